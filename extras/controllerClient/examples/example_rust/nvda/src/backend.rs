@@ -0,0 +1,94 @@
+//! A trait-based abstraction over NVDA's controller client, so downstream code can depend on
+//! [`SpeechBackend`] and swap in a different screen reader without rewriting call sites.
+
+use crate::ssml::Ssml;
+use crate::{
+    braille_message, cancel_speech, clear_on_ssml_mark_reached, set_on_ssml_mark_reached,
+    speak_ssml, speak_text, Result, SpeechPriority, SymbolLevel,
+};
+
+/// Feature flags advertised by a [`SpeechBackend`], since not every backend supports SSML,
+/// braille or mark callbacks.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Capabilities(u32);
+
+impl Capabilities {
+    pub const NONE: Self = Self(0);
+    pub const SSML: Self = Self(1 << 0);
+    pub const BRAILLE: Self = Self(1 << 1);
+    pub const SSML_MARK_CALLBACKS: Self = Self(1 << 2);
+
+    pub fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+impl std::ops::BitOr for Capabilities {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+/// A speech and braille output backend, in the spirit of a single abstraction over multiple
+/// screen reader engines.
+pub trait SpeechBackend {
+    fn speak(&self, text: &str, interrupt: bool) -> Result<()>;
+    fn cancel(&self) -> Result<()>;
+    fn braille(&self, msg: &str) -> Result<()>;
+    fn speak_ssml(
+        &self,
+        ssml: &Ssml,
+        symbol_level: SymbolLevel,
+        priority: SpeechPriority,
+        asynchronous: bool,
+    ) -> Result<()>;
+    /// Registers a closure to be invoked whenever a `<mark>` element is reached while speaking
+    /// SSML. Only meaningful when [`Capabilities::SSML_MARK_CALLBACKS`] is advertised.
+    fn set_on_mark_reached(&self, callback: Box<dyn FnMut(&str) + Send>) -> Result<()>;
+    /// Unregisters any closure set via [`SpeechBackend::set_on_mark_reached`].
+    fn clear_on_mark_reached(&self) -> Result<()>;
+    /// The features this backend advertises support for.
+    fn capabilities(&self) -> Capabilities;
+}
+
+/// A [`SpeechBackend`] implemented on top of NVDA's controller client.
+#[derive(Debug, Default, Copy, Clone)]
+pub struct NvdaController;
+
+impl SpeechBackend for NvdaController {
+    fn speak(&self, text: &str, interrupt: bool) -> Result<()> {
+        speak_text(text, interrupt)
+    }
+
+    fn cancel(&self) -> Result<()> {
+        cancel_speech()
+    }
+
+    fn braille(&self, msg: &str) -> Result<()> {
+        braille_message(msg)
+    }
+
+    fn speak_ssml(
+        &self,
+        ssml: &Ssml,
+        symbol_level: SymbolLevel,
+        priority: SpeechPriority,
+        asynchronous: bool,
+    ) -> Result<()> {
+        speak_ssml(ssml, symbol_level, priority, asynchronous, None::<fn(&str)>)
+    }
+
+    fn set_on_mark_reached(&self, callback: Box<dyn FnMut(&str) + Send>) -> Result<()> {
+        set_on_ssml_mark_reached(callback)
+    }
+
+    fn clear_on_mark_reached(&self) -> Result<()> {
+        clear_on_ssml_mark_reached()
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::SSML | Capabilities::BRAILLE | Capabilities::SSML_MARK_CALLBACKS
+    }
+}
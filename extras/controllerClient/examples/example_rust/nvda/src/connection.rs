@@ -0,0 +1,81 @@
+//! A higher-level handle that caches whether NVDA is running instead of re-entering the
+//! controller client blind on every call.
+
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::{braille_message, cancel_speech, get_process_id, speak_text, test_if_running};
+use crate::{Error, Result};
+
+/// How long a cached "NVDA is running" result is trusted before [`Nvda`] re-probes the
+/// controller client.
+const REPROBE_INTERVAL: Duration = Duration::from_secs(5);
+
+/// A connection to a running NVDA instance.
+///
+/// Unlike the free functions in this crate, which re-enter the controller client DLL on every
+/// call, `Nvda` caches the process id resolved by [`Nvda::connect`] and only re-probes
+/// `test_if_running`/`get_process_id` periodically, short-circuiting with [`Error::NotRunning`]
+/// once NVDA is gone.
+pub struct Nvda {
+    state: Mutex<State>,
+}
+
+struct State {
+    pid: u32,
+    last_checked: Instant,
+}
+
+impl Nvda {
+    /// Checks that NVDA is running and caches its process id.
+    pub fn connect() -> Result<Self> {
+        test_if_running()?;
+        let pid = get_process_id()?;
+        Ok(Self {
+            state: Mutex::new(State {
+                pid,
+                last_checked: Instant::now(),
+            }),
+        })
+    }
+
+    /// Re-probes NVDA if the cached state is older than [`REPROBE_INTERVAL`].
+    ///
+    /// Returns [`Error::Reconnected`] if NVDA's process id changed since it was last cached,
+    /// i.e. NVDA was restarted; the new pid is cached before returning so the next call sees it
+    /// as the baseline.
+    fn ensure_running(&self) -> Result<()> {
+        let mut state = self.state.lock().unwrap();
+        if state.last_checked.elapsed() < REPROBE_INTERVAL {
+            return Ok(());
+        }
+        test_if_running()?;
+        let pid = get_process_id()?;
+        state.last_checked = Instant::now();
+        if pid != state.pid {
+            state.pid = pid;
+            return Err(Error::Reconnected { pid });
+        }
+        Ok(())
+    }
+
+    /// The process id of the NVDA instance this handle last confirmed was running.
+    pub fn process_id(&self) -> u32 {
+        self.state.lock().unwrap().pid
+    }
+
+    pub fn speak(&self, text: &str, interrupt: bool) -> Result<()> {
+        self.ensure_running()?;
+        speak_text(text, interrupt)
+    }
+
+    pub fn cancel(&self) -> Result<()> {
+        self.ensure_running()?;
+        cancel_speech()
+    }
+
+    pub fn braille(&self, msg: &str) -> Result<()> {
+        self.ensure_running()?;
+        braille_message(msg)
+    }
+}
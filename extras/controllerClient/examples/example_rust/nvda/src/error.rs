@@ -0,0 +1,43 @@
+use std::fmt;
+
+use windows::Win32::Foundation::WIN32_ERROR;
+
+/// Errors returned by this crate's wrappers around NVDA's controller client.
+///
+/// Bare `WIN32_ERROR` codes don't let callers distinguish "NVDA isn't running" from other
+/// failures, so the variant says which controller client call failed: any failure of
+/// [`crate::test_if_running`] is [`Error::NotRunning`], failures of the speech calls are
+/// [`Error::SpeechFailed`], failures of [`crate::braille_message`] are [`Error::BrailleFailed`],
+/// and failures of [`crate::get_process_id`] are [`Error::Win32`]. The status code itself does
+/// not select the variant.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// NVDA is not running, so the controller client could not be reached.
+    NotRunning,
+    /// The controller client returned a non-success status while speaking, cancelling speech or
+    /// registering the SSML mark callback.
+    SpeechFailed(WIN32_ERROR),
+    /// The controller client returned a non-success status while displaying a braille message.
+    BrailleFailed(WIN32_ERROR),
+    /// Any other non-success status returned by the controller client.
+    Win32(WIN32_ERROR),
+    /// NVDA is running, but under a different process than the one [`crate::Nvda`] had cached;
+    /// it was restarted since the last call.
+    Reconnected { pid: u32 },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::NotRunning => write!(f, "NVDA is not running"),
+            Error::SpeechFailed(err) => write!(f, "NVDA failed to speak: {err:?}"),
+            Error::BrailleFailed(err) => write!(f, "NVDA failed to display the braille message: {err:?}"),
+            Error::Win32(err) => write!(f, "NVDA controller client error: {err:?}"),
+            Error::Reconnected { pid } => write!(f, "NVDA was restarted (new process id {pid})"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+pub type Result<T> = std::result::Result<T, Error>;
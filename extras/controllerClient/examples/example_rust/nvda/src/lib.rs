@@ -1,3 +1,11 @@
+mod backend;
+mod connection;
+mod error;
+pub mod ssml;
+
+pub use backend::{Capabilities, NvdaController, SpeechBackend};
+pub use connection::Nvda;
+pub use error::{Error, Result};
 pub use nvda_bindgen::{error_status_t, wchar_t};
 use nvda_bindgen::{
     nvdaController_brailleMessage, nvdaController_cancelSpeech, nvdaController_getProcessId,
@@ -5,8 +13,10 @@ use nvda_bindgen::{
     nvdaController_speakText, nvdaController_testIfRunning, onSsmlMarkReachedFuncType,
     SPEECH_PRIORITY, SYMBOL_LEVEL,
 };
+use std::sync::{Mutex, OnceLock};
+use widestring::U16CStr;
 use windows::{
-    core::{Result, HSTRING},
+    core::HSTRING,
     Win32::Foundation::{ERROR_SUCCESS, WIN32_ERROR},
 };
 
@@ -34,7 +44,7 @@ pub type OnSsmlMarkReached = onSsmlMarkReachedFuncType;
 pub fn test_if_running() -> Result<()> {
     let res = WIN32_ERROR(unsafe { nvdaController_testIfRunning() });
     if res != ERROR_SUCCESS {
-        return Err(res.into());
+        return Err(Error::NotRunning);
     }
     Ok(())
 }
@@ -42,7 +52,7 @@ pub fn test_if_running() -> Result<()> {
 pub fn cancel_speech() -> Result<()> {
     let res = WIN32_ERROR(unsafe { nvdaController_cancelSpeech() });
     if res != ERROR_SUCCESS {
-        return Err(res.into());
+        return Err(Error::SpeechFailed(res));
     }
     Ok(())
 }
@@ -54,7 +64,7 @@ pub fn speak_text(text: &str, interrupt: bool) -> Result<()> {
     let text = HSTRING::from(text);
     let res = WIN32_ERROR(unsafe { nvdaController_speakText(text.as_ptr()) });
     if res != ERROR_SUCCESS {
-        return Err(res.into());
+        return Err(Error::SpeechFailed(res));
     }
     Ok(())
 }
@@ -63,7 +73,7 @@ pub fn braille_message(mesage: &str) -> Result<()> {
     let message = HSTRING::from(mesage);
     let res = WIN32_ERROR(unsafe { nvdaController_brailleMessage(message.as_ptr()) });
     if res != ERROR_SUCCESS {
-        return Err(res.into());
+        return Err(Error::BrailleFailed(res));
     }
     Ok(())
 }
@@ -72,7 +82,7 @@ pub fn get_process_id() -> Result<u32> {
     let mut pid: u32 = 0;
     let res = WIN32_ERROR(unsafe { nvdaController_getProcessId(&mut pid) });
     if res != ERROR_SUCCESS {
-        return Err(res.into());
+        return Err(Error::Win32(res));
     }
     Ok(pid)
 }
@@ -80,20 +90,69 @@ pub fn get_process_id() -> Result<u32> {
 fn set_on_ssml_mark_reached_callback(callback: OnSsmlMarkReached) -> Result<()> {
     let res = WIN32_ERROR(unsafe { nvdaController_setOnSsmlMarkReachedCallback(callback) });
     if res != ERROR_SUCCESS {
-        return Err(res.into());
+        return Err(Error::SpeechFailed(res));
     }
     Ok(())
 }
 
-pub fn speak_ssml(
+type SsmlMarkReachedCallback = Box<dyn FnMut(&str) + Send>;
+
+static SSML_MARK_REACHED_CALLBACK: OnceLock<Mutex<Option<SsmlMarkReachedCallback>>> =
+    OnceLock::new();
+
+fn ssml_mark_reached_callback() -> &'static Mutex<Option<SsmlMarkReachedCallback>> {
+    SSML_MARK_REACHED_CALLBACK.get_or_init(|| Mutex::new(None))
+}
+
+extern "C" fn ssml_mark_reached_trampoline(mark_name: *const wchar_t) {
+    let mark_name = match unsafe { U16CStr::from_ptr_str(mark_name as *const u16) }.to_string() {
+        Ok(mark_name) => mark_name,
+        Err(_) => return,
+    };
+    if let Some(callback) = ssml_mark_reached_callback().lock().unwrap().as_mut() {
+        callback(&mark_name);
+    }
+}
+
+/// Registers a safe Rust closure to be invoked whenever NVDA reaches a `<mark>` element
+/// while speaking SSML, instead of requiring callers to hand NVDA a raw `extern "C"` function
+/// pointer. Stays registered until [`clear_on_ssml_mark_reached`] is called, so it is safe to
+/// use with asynchronous [`speak_ssml`] calls.
+pub fn set_on_ssml_mark_reached<F>(f: F) -> Result<()>
+where
+    F: FnMut(&str) + Send + 'static,
+{
+    *ssml_mark_reached_callback().lock().unwrap() = Some(Box::new(f));
+    set_on_ssml_mark_reached_callback(Some(ssml_mark_reached_trampoline))
+}
+
+/// Unregisters any closure set via [`set_on_ssml_mark_reached`].
+pub fn clear_on_ssml_mark_reached() -> Result<()> {
+    *ssml_mark_reached_callback().lock().unwrap() = None;
+    set_on_ssml_mark_reached_callback(None)
+}
+
+pub fn speak_ssml<F>(
     ssml: &str,
     symbol_level: SymbolLevel,
     priority: SpeechPriority,
     asynchronous: bool,
-    callback: onSsmlMarkReachedFuncType,
-) -> Result<()> {
-    if callback.is_some() {
-        set_on_ssml_mark_reached_callback(callback)?
+    callback: Option<F>,
+) -> Result<()>
+where
+    F: FnMut(&str) + Send + 'static,
+{
+    let has_callback = callback.is_some();
+    // For a synchronous call, the closure passed here is only meant to live for the duration of
+    // this call; whatever was previously registered (e.g. a persistent callback for async
+    // speech) must come back afterwards rather than being wiped to `None`.
+    let previous = if has_callback {
+        ssml_mark_reached_callback().lock().unwrap().take()
+    } else {
+        None
+    };
+    if let Some(callback) = callback {
+        set_on_ssml_mark_reached(callback)?
     }
     let ssml = HSTRING::from(ssml);
     let res = WIN32_ERROR(unsafe {
@@ -104,11 +163,16 @@ pub fn speak_ssml(
             asynchronous.into(),
         )
     });
-    if res != ERROR_SUCCESS {
-        return Err(res.into());
+    // Asynchronous calls report marks after this function returns, so the callback must stay
+    // registered; callers are responsible for clearing it via `clear_on_ssml_mark_reached`.
+    if has_callback && !asynchronous {
+        match previous {
+            Some(previous) => *ssml_mark_reached_callback().lock().unwrap() = Some(previous),
+            None => clear_on_ssml_mark_reached()?,
+        }
     }
-    if callback.is_some() {
-        set_on_ssml_mark_reached_callback(None)?
+    if res != ERROR_SUCCESS {
+        return Err(Error::SpeechFailed(res));
     }
     Ok(())
 }
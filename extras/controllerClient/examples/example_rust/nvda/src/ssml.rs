@@ -0,0 +1,158 @@
+//! A programmatic builder for the subset of SSML that NVDA's speech synthesizer honors, so
+//! callers don't have to hand-write and escape XML strings for [`crate::speak_ssml`].
+
+use std::fmt::Write as _;
+use std::time::Duration;
+
+/// A well-formed SSML document produced by [`SsmlBuilder::build`].
+///
+/// Text content is escaped and `<prosody>` attributes are normalized to values NVDA's SSML
+/// processor accepts. Dereferences to `&str` so it can be passed anywhere a plain SSML string is
+/// expected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Ssml(String);
+
+impl std::ops::Deref for Ssml {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl AsRef<str> for Ssml {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+/// Pitch, rate and volume for a `<prosody>` scope. `None` leaves an attribute unset, which tells
+/// NVDA to inherit the enclosing value.
+///
+/// * `rate` is a speaking-rate multiplier expressed as a percentage, e.g. `150.0` for 1.5x speed.
+/// * `pitch` is a relative change expressed as a signed percentage, e.g. `-10.0` for 10% lower.
+/// * `volume` is an absolute level from `0.0` (silent) to `100.0` (loudest).
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Prosody {
+    pub rate: Option<f32>,
+    pub pitch: Option<f32>,
+    pub volume: Option<f32>,
+}
+
+/// Builds a well-formed `<speak>` document out of the elements NVDA's SSML processor
+/// understands: `<prosody>`, `<break>`, `<emphasis>`, `<say-as>` and `<mark>`.
+///
+/// ```
+/// # use nvda::ssml::SsmlBuilder;
+/// # use std::time::Duration;
+/// let ssml = SsmlBuilder::new()
+///     .text("Hello")
+///     .mark("m1")
+///     .break_for(Duration::from_millis(200))
+///     .text("world")
+///     .build();
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct SsmlBuilder {
+    body: String,
+}
+
+impl SsmlBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends escaped text content.
+    pub fn text(mut self, text: &str) -> Self {
+        escape_into(&mut self.body, text);
+        self
+    }
+
+    /// Emits a `<mark name="..."/>` element, e.g. to line up with
+    /// [`crate::set_on_ssml_mark_reached`].
+    pub fn mark(mut self, name: &str) -> Self {
+        write!(self.body, r#"<mark name="{}"/>"#, Escaped(name)).unwrap();
+        self
+    }
+
+    /// Emits a `<break time="...ms"/>` element.
+    pub fn break_for(mut self, duration: Duration) -> Self {
+        write!(self.body, r#"<break time="{}ms"/>"#, duration.as_millis()).unwrap();
+        self
+    }
+
+    /// Emits a `<say-as interpret-as="...">text</say-as>` element.
+    pub fn say_as(mut self, interpret_as: &str, text: &str) -> Self {
+        write!(
+            self.body,
+            r#"<say-as interpret-as="{}">{}</say-as>"#,
+            Escaped(interpret_as),
+            Escaped(text)
+        )
+        .unwrap();
+        self
+    }
+
+    /// Wraps `content` in an `<emphasis level="...">` scope.
+    pub fn emphasis(mut self, level: &str, content: impl FnOnce(SsmlBuilder) -> SsmlBuilder) -> Self {
+        write!(self.body, r#"<emphasis level="{}">"#, Escaped(level)).unwrap();
+        let inner = content(SsmlBuilder::new());
+        self.body.push_str(&inner.body);
+        self.body.push_str("</emphasis>");
+        self
+    }
+
+    /// Wraps `content` in a `<prosody>` scope, emitting only the attributes that are `Some`.
+    pub fn prosody(
+        mut self,
+        prosody: Prosody,
+        content: impl FnOnce(SsmlBuilder) -> SsmlBuilder,
+    ) -> Self {
+        self.body.push_str("<prosody");
+        if let Some(rate) = prosody.rate {
+            // `rate` is an unsigned percentage of the default speaking rate.
+            write!(self.body, r#" rate="{}%""#, rate.clamp(20.0, 400.0)).unwrap();
+        }
+        if let Some(pitch) = prosody.pitch {
+            // `pitch` must be a signed relative change; a bare percentage is not valid SSML.
+            write!(self.body, r#" pitch="{:+}%""#, pitch.clamp(-50.0, 50.0)).unwrap();
+        }
+        if let Some(volume) = prosody.volume {
+            // `volume` is an absolute level from 0 to 100, without a percent sign.
+            write!(self.body, r#" volume="{}""#, volume.clamp(0.0, 100.0)).unwrap();
+        }
+        self.body.push('>');
+        let inner = content(SsmlBuilder::new());
+        self.body.push_str(&inner.body);
+        self.body.push_str("</prosody>");
+        self
+    }
+
+    /// Finishes the document, wrapping the accumulated body in a `<speak>` root element.
+    pub fn build(self) -> Ssml {
+        Ssml(format!("<speak>{}</speak>", self.body))
+    }
+}
+
+struct Escaped<'a>(&'a str);
+
+impl std::fmt::Display for Escaped<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut buf = String::new();
+        escape_into(&mut buf, self.0);
+        f.write_str(&buf)
+    }
+}
+
+fn escape_into(out: &mut String, text: &str) {
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            c => out.push(c),
+        }
+    }
+}